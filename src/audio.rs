@@ -0,0 +1,91 @@
+// Whale Simulator (C) 2022 Sadie Powell <sadie@witchery.services>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::io::Cursor;
+
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+
+/// The embedded sample played when krill is eaten.
+const CHOMP: &'static [u8] = include_bytes!("../assets/chomp.wav");
+
+/// The embedded sample played when the player is harpooned.
+const SPLASH: &'static [u8] = include_bytes!("../assets/splash.wav");
+
+/// The embedded sample played when a new boat spawns.
+const BOAT_HORN: &'static [u8] = include_bytes!("../assets/boat_horn.wav");
+
+/// A sound cue that can be played on a key game event.
+pub enum Sound {
+    /// The player has eaten a krill.
+    Chomp,
+
+    /// The player has been harpooned.
+    Harpooned,
+
+    /// A new boat has spawned.
+    BoatHorn,
+}
+
+impl Sound {
+    /// Retrieves the embedded sample data for this sound.
+    fn bytes(&self) -> &'static [u8] {
+        match self {
+            Sound::Chomp => CHOMP,
+            Sound::Harpooned => SPLASH,
+            Sound::BoatHorn => BOAT_HORN,
+        }
+    }
+}
+
+/// Plays short sound cues without blocking the game loop.
+///
+/// Kept entirely separate from the output stream it was built from, so playback
+/// failures never bubble up and interrupt a round in progress.
+pub struct SoundPlayer {
+    /// The output stream backing playback; must be kept alive for as long as
+    /// the player is in use.
+    _stream: OutputStream,
+
+    /// The handle used to spawn new playback sinks.
+    handle: OutputStreamHandle,
+}
+
+impl SoundPlayer {
+    /// Attempts to initialize the default audio device, returning `None` if no
+    /// device is available.
+    pub fn new() -> Option<Self> {
+        let (stream, handle) = OutputStream::try_default().ok()?;
+        Some(SoundPlayer { _stream: stream, handle })
+    }
+
+    /// Plays a sound cue, returning immediately without waiting for it to finish.
+    pub fn play(&self, sound: Sound) {
+        let Ok(sink) = Sink::try_new(&self.handle) else {
+            return;
+        };
+        let Ok(source) = Decoder::new(Cursor::new(sound.bytes())) else {
+            return;
+        };
+        sink.append(source);
+        sink.detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_sound_selects_its_own_sample() {
+        assert_eq!(Sound::Chomp.bytes(), CHOMP);
+        assert_eq!(Sound::Harpooned.bytes(), SPLASH);
+        assert_eq!(Sound::BoatHorn.bytes(), BOAT_HORN);
+    }
+
+    #[test]
+    fn embedded_samples_are_valid_audio() {
+        for sound in [Sound::Chomp, Sound::Harpooned, Sound::BoatHorn] {
+            assert!(Decoder::new(Cursor::new(sound.bytes())).is_ok());
+        }
+    }
+}
@@ -23,6 +23,12 @@ const RATIO_BAD: &'static str = "\u{1F4C9}";
 /// The emoji used to represent good ratios.
 const RATIO_GOOD: &'static str = "\u{1F4C8}";
 
+/// The emoji used to represent an okay hunger level.
+const HUNGER_OKAY: &'static str = "\u{1F37D}";
+
+/// The emoji used to represent a dangerously high hunger level.
+const HUNGER_STARVING: &'static str = "\u{26A0}";
+
 /// The emoji used to represent the player.
 const WHALE_ALIVE: &'static str = "\u{1F40B}";
 
@@ -37,6 +43,18 @@ pub enum Direction {
     Right,
 }
 
+/// The amount hunger increases by on each hunger tick.
+const HUNGER_INCREMENT: u8 = 4;
+
+/// The amount hunger decreases by when a krill is eaten.
+const HUNGER_KRILL_RELIEF: u8 = 15;
+
+/// The hunger level at which the player starves to death.
+pub const HUNGER_MAX: u8 = 100;
+
+/// The hunger level above which the player's movement starts to slow.
+const HUNGER_SLOW_THRESHOLD: u8 = 75;
+
 /// Represents the whale player.
 pub struct Player {
     // The time the player is harpooned until.
@@ -45,14 +63,44 @@ pub struct Player {
     /// The number of times the player has been harpooned.
     pub harpoon_count: usize,
 
+    /// How hungry the player currently is, from 0 (full) to 100 (starving).
+    pub hunger: u8,
+
     /// The number of krill the player has eaten.
     pub krill_eaten: usize,
 
+    /// The player's last horizontal step, used by boats to lead their shots.
+    heading: i16,
+
     /// The location of the player.
     position: Point,
+
+    /// The time the player last moved, used to throttle movement whilst famished.
+    slowed_until: Instant,
 }
 
 impl Player {
+    /// Increases the player's hunger by the standard tick amount.
+    ///
+    /// Returns whether the player has starved to death.
+    pub fn feel_hunger(&mut self) -> bool {
+        self.hunger = self.hunger.saturating_add(HUNGER_INCREMENT).min(HUNGER_MAX);
+        self.hunger >= HUNGER_MAX
+    }
+
+    /// Relieves the player's hunger after eating a krill.
+    pub fn feed(&mut self) {
+        self.hunger = self.hunger.saturating_sub(HUNGER_KRILL_RELIEF);
+    }
+
+    /// Retrieves the emoji used to represent the player's hunger.
+    pub fn hunger_emoji(&self) -> &'static str {
+        if self.hunger >= HUNGER_SLOW_THRESHOLD {
+            HUNGER_STARVING
+        } else {
+            HUNGER_OKAY
+        }
+    }
     /// Retrieves the current emoji used to render the player.
     pub fn emoji(&self) -> &'static str {
         if self.harpooned_until > Instant::now() {
@@ -73,6 +121,12 @@ impl Player {
         if self.harpooned_until > Instant::now() {
             return; // Can't move whilst harpooned.
         }
+        if self.hunger >= HUNGER_SLOW_THRESHOLD {
+            if self.slowed_until > Instant::now() {
+                return; // Too famished to move again yet.
+            }
+            self.slowed_until = Instant::now() + Duration::from_millis(150);
+        }
         match direction {
             Direction::Up => {
                 if self.position.1 > 6 {
@@ -87,26 +141,42 @@ impl Player {
             Direction::Left => {
                 if self.position.0 >= 2 {
                     self.position.0 -= 2;
+                    self.heading = -2;
                 }
             }
             Direction::Right => {
                 if self.position.0 + 2 <= size.0 {
                     self.position.0 += 2;
+                    self.heading = 2;
                 }
             }
         }
     }
 
+    /// Clamps the player's position back into the bounds of a resized screen.
+    pub fn clamp(&mut self, size: &Point) {
+        self.position.0 = self.position.0.min(size.0.saturating_sub(2));
+        self.position.1 = self.position.1.clamp(7.min(size.1), size.1.max(7));
+    }
+
     /// Creates a new player entity.
     pub fn new(dimensions: &Point) -> Self {
         Player {
             krill_eaten: 0,
             harpoon_count: 0,
             harpooned_until: Instant::now(),
+            heading: 0,
+            hunger: 0,
             position: (dimensions.0 / 2, dimensions.1 / 2 + 3),
+            slowed_until: Instant::now(),
         }
     }
 
+    /// Retrieves the player's last horizontal step, for predicting where they're headed.
+    pub fn heading(&self) -> i16 {
+        return self.heading;
+    }
+
     /// Retrieves the location of the player.
     pub fn position(&self) -> &Point {
         return &self.position;
@@ -114,10 +184,19 @@ impl Player {
 
     /// Calculates the player's krill/death ratio (hehehe).
     pub fn ratio(&self) -> String {
-        if self.harpoon_count == 0 {
+        if self.ratio_value().is_infinite() {
             return "\u{221E}".to_string(); // Infinity.
         }
-        return format!("{:.3}", self.krill_eaten as f32 / self.harpoon_count as f32);
+        return format!("{:.3}", self.ratio_value());
+    }
+
+    /// Calculates the player's krill/death ratio as a number, for ranking scores.
+    pub fn ratio_value(&self) -> f32 {
+        if self.harpoon_count == 0 {
+            f32::INFINITY
+        } else {
+            self.krill_eaten as f32 / self.harpoon_count as f32
+        }
     }
 
     /// Retrieves the current emoji used to render the ratio graph.
@@ -137,6 +216,12 @@ pub struct Krill {
 }
 
 impl Krill {
+    /// Clamps the krill's position back into the bounds of a resized screen.
+    pub fn clamp(&mut self, size: &Point) {
+        self.position.0 = self.position.0.min(size.0.saturating_sub(2));
+        self.position.1 = self.position.1.clamp(7.min(size.1), size.1.max(7));
+    }
+
     /// Creates a new krill entity.
     pub fn new(size: Point) -> Self {
         let pos_x = thread_rng().gen_range(0..size.0 / 2) * 2;
@@ -160,6 +245,11 @@ pub struct Boat {
 }
 
 impl Boat {
+    /// Clamps the boat's position back into the bounds of a resized screen.
+    pub fn clamp(&mut self, size: &Point) {
+        self.position = self.position.min(size.0.saturating_sub(2));
+    }
+
     fn next_harpoon_spawn() -> Instant {
         Instant::now() + Duration::from_millis(thread_rng().gen_range(5_000..10_000))
     }
@@ -193,23 +283,49 @@ impl Boat {
     }
 }
 
+/// The row at which harpoons are spawned, level with the boats.
+const HARPOON_SPAWN_ROW: u16 = 6;
+
 /// Represents a spikey harpoon..
 pub struct Harpoon {
+    /// The horizontal step applied to the harpoon on each migration.
+    dx: i16,
+
     /// The location of the harpoon.
     position: Point,
 }
 
 impl Harpoon {
-    /// Creates a new boat entity.
-    pub fn new(boat: &Boat) -> Self {
+    /// Clamps the harpoon's position back into the bounds of a resized screen.
+    pub fn clamp(&mut self, size: &Point) {
+        self.position.0 = self.position.0.min(size.0.saturating_sub(1));
+        self.position.1 = self.position.1.min(size.1.saturating_sub(1));
+    }
+
+    /// Retrieves the horizontal step applied to the harpoon on each migration.
+    pub fn dx(&self) -> i16 {
+        return self.dx;
+    }
+
+    /// Creates a new harpoon, leading the given target by `accuracy` (0.0 is a dumb
+    /// straight-down shot, 1.0 is a near-perfect interception). `heading` is the
+    /// target's last horizontal step, used to extrapolate where it'll be by the
+    /// time the harpoon falls that far, rather than just where it is right now.
+    pub fn new(boat: &Boat, target: &Point, heading: i16, accuracy: f32) -> Self {
+        let rows_to_fall = target.1.saturating_sub(HARPOON_SPAWN_ROW).max(1) as f32;
+        let predicted_column = target.0 as f32 + heading as f32 * rows_to_fall;
+        let column_delta = predicted_column - boat.position() as f32;
+        let ideal_dx = column_delta / rows_to_fall;
         Harpoon {
-            position: (boat.position(), 6),
+            dx: (ideal_dx * accuracy.clamp(0.0, 1.0)).round() as i16,
+            position: (boat.position(), HARPOON_SPAWN_ROW),
         }
     }
 
-    /// Migrates the harpoon down the screen.
+    /// Migrates the harpoon down and across the screen.
     pub fn migrate(&mut self) {
         self.position.1 += 1;
+        self.position.0 = self.position.0.saturating_add_signed(self.dx);
     }
 
     /// Retrieves the location of the harpoon.
@@ -217,3 +333,22 @@ impl Harpoon {
         return &self.position;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_clamp_does_not_panic_below_min_height() {
+        let mut player = Player::new(&(40, 20));
+        player.clamp(&(40, 5));
+        assert!(player.position().1 <= 7);
+    }
+
+    #[test]
+    fn krill_clamp_does_not_panic_below_min_height() {
+        let mut krill = Krill::new((40, 20));
+        krill.clamp(&(40, 5));
+        assert!(krill.position().1 <= 7);
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use std::io::{StdoutLock, Write};
+use std::thread;
 use std::time::{Duration, Instant};
 
 use rand::{thread_rng, Rng};
@@ -12,7 +13,11 @@ use termion::raw::{IntoRawMode, RawTerminal};
 use termion::AsyncReader;
 use termion::{clear, cursor};
 
+#[cfg(feature = "audio")]
+use crate::audio::{Sound, SoundPlayer};
 use crate::entity::{Boat, Direction, Harpoon, Krill, Player, BOAT, HARPOON, KRILL};
+use crate::grammar;
+use crate::leaderboard;
 
 /// The emoji used to represent deaths.
 const DEATH: &'static str = "\u{1F480}";
@@ -20,6 +25,12 @@ const DEATH: &'static str = "\u{1F480}";
 /// The raw control sequence to terminate a line.
 const LINE_TERMINATOR: &'static str = "\r\n";
 
+/// The minimum terminal height we can render the UI and play space in.
+const MIN_HEIGHT: u16 = 15;
+
+/// The minimum terminal width we can render the UI and play space in.
+const MIN_WIDTH: u16 = 20;
+
 /// The emoji used to represent the sea.
 const WAVE: &'static str = "\u{1F30A}";
 
@@ -31,6 +42,15 @@ pub struct GameState<'a> {
     /// Whether the game is actively runing.
     alive: bool,
 
+    /// The sound player used for audio cues, or `None` if the `audio` feature is
+    /// disabled or no output device could be initialized.
+    #[cfg(feature = "audio")]
+    audio: Option<SoundPlayer>,
+
+    /// How perfectly boats lead the player when firing harpoons, from 0.0 (straight
+    /// down) to 1.0 (near-perfect interception).
+    accuracy: f32,
+
     /// Boats which have been spawned.
     boats: Vec<Boat>,
 
@@ -52,12 +72,18 @@ pub struct GameState<'a> {
     /// The time at which harpoons will be moved next.
     next_harpoon_move: Instant,
 
+    /// The time at which hunger will be incremented next.
+    next_hunger_tick: Instant,
+
     /// The time at which the next krill will be spawned.
     next_krill: Instant,
 
     /// The size of the terminal we are drawing to.
     size: Point,
 
+    /// Whether the player starved to death, rather than being harpooned.
+    starved: bool,
+
     /// The standard input stream.
     stdin: Keys<AsyncReader>,
 
@@ -76,6 +102,7 @@ impl<'a> GameState<'a> {
         write!(self.stdout, "  {}  {: <5}", KRILL, self.player.krill_eaten).unwrap();
         write!(self.stdout, "  {}  {: <5}", DEATH, self.player.harpoon_count).unwrap();
         write!(self.stdout, "  {}  {}", self.player.ratio_emoji(), self.player.ratio()).unwrap();
+        write!(self.stdout, "  {}  {: <3}%", self.player.hunger_emoji(), self.player.hunger).unwrap();
 
         // Wave line.
         write!(self.stdout, "{}", cursor::Goto(1, 5)).unwrap();
@@ -112,19 +139,95 @@ impl<'a> GameState<'a> {
         self.stdout.flush().unwrap();
     }
 
+    /// Shows a notice asking the player to enlarge their terminal whilst it is
+    /// below the minimum playable size.
+    fn draw_too_small(&mut self) {
+        write!(self.stdout, "{}{}", clear::All, cursor::Goto(1, 1)).unwrap();
+        write!(self.stdout, "Please enlarge your terminal to at least {}x{}...", MIN_WIDTH, MIN_HEIGHT).unwrap();
+        self.stdout.flush().unwrap();
+    }
+
+    /// Plays a sound cue if an output device was successfully initialized.
+    #[cfg(feature = "audio")]
+    fn play_sound(&self, sound: Sound) {
+        if let Some(player) = &self.audio {
+            player.play(sound);
+        }
+    }
+
+    /// Polls the terminal size and, if it has changed, re-clamps every entity
+    /// into the new bounds.
+    fn resize(&mut self) {
+        let size = match termion::terminal_size() {
+            Ok(size) => size,
+            Err(_) => return,
+        };
+
+        if size == self.size {
+            return;
+        }
+
+        self.size = size;
+        self.player.clamp(&size);
+        for boat in &mut self.boats {
+            boat.clamp(&size);
+        }
+        for krill in &mut self.krill {
+            krill.clamp(&size);
+        }
+        for harpoon in &mut self.harpoons {
+            harpoon.clamp(&size);
+        }
+    }
+
     /// Prints statistics and cleans up the terminal.
     pub fn end(&mut self) {
         // Show the game statistics.
         write!(self.stdout, "{}{}", clear::All, cursor::Goto(1, 1)).unwrap();
         write!(self.stdout, "Thanks for playing Whale Simulator!{}", LINE_TERMINATOR).unwrap();
-        write!(self.stdout, "You ate {} delicious krill and were harpooned {} time(s).{}", self.player.krill_eaten, self.player.harpoon_count, LINE_TERMINATOR).unwrap();
-        write!(self.stdout, "Your krill/death ratio was {}.{}", self.player.ratio(), LINE_TERMINATOR).unwrap();
+        let grammar = grammar::end_of_game(self.player.krill_eaten, self.player.harpoon_count, self.player.ratio(), self.starved);
+        write!(self.stdout, "{}{}", grammar.flatten("origin"), LINE_TERMINATOR).unwrap();
+
+        // Record a new high score if this round earned one.
+        let mut leaderboard = leaderboard::Leaderboard::load();
+        if leaderboard.qualifies(self.player.krill_eaten, self.player.harpoon_count) {
+            let name = self.prompt("New high score! Enter a 3-letter name: ");
+            let name: String = name.chars().filter(|c| c.is_alphanumeric()).take(3).collect::<String>().to_uppercase();
+            leaderboard.insert(leaderboard::Entry::new(name, self.player.krill_eaten, self.player.harpoon_count));
+            if let Err(err) = leaderboard.save() {
+                write!(self.stdout, "Unable to save the leaderboard: {}.{}", err, LINE_TERMINATOR).unwrap();
+            }
+        }
+        write!(self.stdout, "{}{}", LINE_TERMINATOR, leaderboard).unwrap();
 
         // Attempt to reset the terminal back to how it was before.
         write!(self.stdout, "{}", cursor::Show).unwrap();
         self.stdout.flush().unwrap();
     }
 
+    /// Blocks until the player has entered a line of text, echoing keystrokes at
+    /// the bottom of the screen, and returns what they typed.
+    fn prompt(&mut self, message: &str) -> String {
+        let mut input = String::new();
+        let row = self.size.1;
+
+        loop {
+            write!(self.stdout, "{}{}{}{}", cursor::Goto(1, row), clear::CurrentLine, message, input).unwrap();
+            self.stdout.flush().unwrap();
+
+            match self.stdin.next() {
+                Some(Ok(Key::Char('\n'))) => break,
+                Some(Ok(Key::Char(c))) => input.push(c),
+                Some(Ok(Key::Backspace)) => {
+                    input.pop();
+                }
+                _ => thread::sleep(Duration::from_millis(30)),
+            }
+        }
+
+        input
+    }
+
     /// Handles keyboard input on tick.
     fn input(&mut self) {
         while let Some(Ok(key)) = self.stdin.next() {
@@ -147,7 +250,7 @@ impl<'a> GameState<'a> {
         }
     }
 
-    pub fn new(stdout: StdoutLock<'a>) -> Result<Self, String> {
+    pub fn new(stdout: StdoutLock<'a>, accuracy: f32) -> Result<Self, String> {
         let async_stdin = termion::async_stdin().keys();
 
         let raw_stdout = stdout
@@ -158,21 +261,26 @@ impl<'a> GameState<'a> {
             .map_err(|err| format!("Unable to retrieve the terminal size: {}", err))?;
 
         // Check we have space to render the UI and give a bit of play space.
-        if size.0 < 20 || size.1 < 15 {
-            return Err(format!("The terminal must be at least 20x15 (currently {}x{})", size.0, size.1));
+        if size.0 < MIN_WIDTH || size.1 < MIN_HEIGHT {
+            return Err(format!("The terminal must be at least {}x{} (currently {}x{})", MIN_WIDTH, MIN_HEIGHT, size.0, size.1));
         }
 
         Ok(GameState {
             alive: true,
+            #[cfg(feature = "audio")]
+            audio: SoundPlayer::new(),
+            accuracy: accuracy.clamp(0.0, 1.0),
             boats: Vec::new(),
             harpoons: Vec::new(),
             krill: Vec::new(),
             next_boat_move: Instant::now(),
             next_boat_spawn: Instant::now(),
             next_harpoon_move: Instant::now(),
+            next_hunger_tick: Instant::now() + Duration::from_millis(1_500),
             next_krill: Instant::now(),
             player: Player::new(&size),
             size,
+            starved: false,
             stdin: async_stdin,
             stdout: raw_stdout,
         })
@@ -185,12 +293,29 @@ impl<'a> GameState<'a> {
         self.harpoons.retain(|h| h.position() != self.player.position());
         if harpoon_count - self.harpoons.len() != 0 {
             self.player.harpoon();
+            #[cfg(feature = "audio")]
+            self.play_sound(Sound::Harpooned);
         }
 
         // Check if the player has eaten any krill.
         let krill_count = self.krill.len();
         self.krill.retain(|k| k.position() != self.player.position());
-        self.player.krill_eaten +=  krill_count - self.krill.len();
+        let krill_eaten = krill_count - self.krill.len();
+        self.player.krill_eaten += krill_eaten;
+        for _ in 0..krill_eaten {
+            self.player.feed();
+            #[cfg(feature = "audio")]
+            self.play_sound(Sound::Chomp);
+        }
+
+        // Check if the player has grown hungrier.
+        if self.next_hunger_tick < Instant::now() {
+            if self.player.feel_hunger() {
+                self.alive = false;
+                self.starved = true;
+            }
+            self.next_hunger_tick = Instant::now() + Duration::from_millis(1_500);
+        }
 
         // Check if any boats need to be culled or moved.
         if self.next_boat_move < Instant::now() {
@@ -203,7 +328,11 @@ impl<'a> GameState<'a> {
 
         // Check if an harpoons need to be culled or moved.
         if self.next_harpoon_move < Instant::now() {
-            self.harpoons.retain(|b| b.position().1 + 1 < self.size.1);
+            self.harpoons.retain(|h| {
+                let pos = h.position();
+                let next_x = pos.0 as i32 + h.dx() as i32;
+                pos.1 + 1 < self.size.1 && next_x >= 0 && next_x < self.size.0 as i32
+            });
             for harpoon in &mut self.harpoons {
                 harpoon.migrate();
             }
@@ -220,19 +349,28 @@ impl<'a> GameState<'a> {
         // Potentially spawn some new boats.
         if self.next_boat_spawn < Instant::now() {
             self.boats.push(Boat::new());
+            #[cfg(feature = "audio")]
+            self.play_sound(Sound::BoatHorn);
             self.next_boat_spawn = Instant::now() + Duration::from_millis(thread_rng().gen_range(2_500..5_000));
         }
         // Potentially spawn some new harpoons.
         for boat in &mut self.boats {
             if boat.harpoon_time() {
-                self.harpoons.push(Harpoon::new(boat))
+                self.harpoons.push(Harpoon::new(boat, self.player.position(), self.player.heading(), self.accuracy))
             }
         }
     }
 
     /// Called every time the game needs to update.
     pub fn tick(&mut self) -> bool {
+        self.resize();
         self.input();
+
+        if self.size.0 < MIN_WIDTH || self.size.1 < MIN_HEIGHT {
+            self.draw_too_small();
+            return self.alive;
+        }
+
         self.think();
         self.draw();
         self.alive
@@ -0,0 +1,128 @@
+// Whale Simulator (C) 2022 Sadie Powell <sadie@witchery.services>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::collections::BTreeMap;
+
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+/// The maximum number of times a symbol may be expanded before we give up and
+/// return whatever text we have, to guard against accidentally-recursive rules.
+const MAX_DEPTH: usize = 32;
+
+/// A tiny tracery-style text grammar, expanding `#rule#` placeholders by randomly
+/// picking one of the rule's expansions until no placeholders remain.
+pub struct Grammar {
+    /// The rules making up the grammar, keyed by name.
+    rules: BTreeMap<String, Vec<String>>,
+}
+
+impl Grammar {
+    /// Expands the rule named `origin` into a finished piece of text.
+    pub fn flatten(&self, origin: &str) -> String {
+        self.expand(&format!("#{}#", origin), 0)
+    }
+
+    /// Recursively substitutes every `#symbol#` placeholder found in `text`.
+    fn expand(&self, text: &str, depth: usize) -> String {
+        if depth >= MAX_DEPTH {
+            return text.to_string();
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('#') {
+            result.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+
+            let Some(end) = rest.find('#') else {
+                // An unmatched '#'; keep it as-is and stop looking.
+                result.push('#');
+                result.push_str(rest);
+                return result;
+            };
+
+            let symbol = &rest[..end];
+            rest = &rest[end + 1..];
+
+            match self.rules.get(symbol) {
+                Some(expansions) => {
+                    let expansion = expansions.choose(&mut thread_rng()).map(String::as_str).unwrap_or("");
+                    result.push_str(&self.expand(expansion, depth + 1));
+                }
+                None => {
+                    // Not a known rule; leave the placeholder untouched.
+                    result.push('#');
+                    result.push_str(symbol);
+                    result.push('#');
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Creates a new grammar from the given rules.
+    pub fn new(rules: BTreeMap<String, Vec<String>>) -> Self {
+        Grammar { rules }
+    }
+}
+
+/// Builds the grammar used to narrate the end of a round, with `krill`, `deaths`
+/// and `ratio` pre-resolved as terminal rules from the final statistics.
+pub fn end_of_game(krill: usize, deaths: usize, ratio: String, starved: bool) -> Grammar {
+    let mut rules = BTreeMap::new();
+
+    rules.insert("origin".to_string(), vec!["#opener# You devoured #krill# krill and #death_clause#, for a krill/death ratio of #ratio#.".to_string()]);
+
+    rules.insert(
+        "opener".to_string(),
+        vec![
+            "Another day, another ocean conquered.".to_string(),
+            "The tide goes out, and so does your story.".to_string(),
+            "The fishing fleet will remember this one.".to_string(),
+            "Somewhere, a krill shoal breathes a sigh of relief.".to_string(),
+        ],
+    );
+
+    rules.insert(
+        "death_clause".to_string(),
+        if starved {
+            vec!["ultimately starved with an empty belly".to_string()]
+        } else if deaths == 0 {
+            vec!["were never once harpooned".to_string()]
+        } else {
+            vec![format!("were harpooned {} time(s)", deaths)]
+        },
+    );
+
+    rules.insert("krill".to_string(), vec![krill.to_string()]);
+    rules.insert("ratio".to_string(), vec![ratio]);
+
+    Grammar::new(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_leaves_an_unknown_symbol_untouched() {
+        let grammar = Grammar::new(BTreeMap::new());
+        assert_eq!(grammar.flatten("missing"), "#missing#");
+    }
+
+    #[test]
+    fn expand_stops_at_the_depth_cap_instead_of_looping_forever() {
+        let mut rules = BTreeMap::new();
+        rules.insert("origin".to_string(), vec!["#origin#".to_string()]);
+        let grammar = Grammar::new(rules);
+        assert_eq!(grammar.flatten("origin"), "#origin#");
+    }
+
+    #[test]
+    fn end_of_game_weaves_the_ratio_into_the_origin_rule() {
+        let grammar = end_of_game(3, 1, "3.000".to_string(), false);
+        assert!(grammar.flatten("origin").contains("3.000"));
+    }
+}
@@ -0,0 +1,163 @@
+// Whale Simulator (C) 2022 Sadie Powell <sadie@witchery.services>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+/// How many entries the leaderboard keeps.
+const MAX_ENTRIES: usize = 10;
+
+/// A single recorded round, ranked by krill/death ratio.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Entry {
+    /// The date the round was played.
+    pub date: String,
+
+    /// The number of times the player was harpooned.
+    pub harpoon_count: usize,
+
+    /// The number of krill the player ate.
+    pub krill_eaten: usize,
+
+    /// The name the player entered for this entry.
+    pub name: String,
+}
+
+impl Entry {
+    /// Creates a new entry stamped with today's date.
+    pub fn new(name: String, krill_eaten: usize, harpoon_count: usize) -> Self {
+        Entry {
+            date: Local::now().format("%Y-%m-%d").to_string(),
+            harpoon_count,
+            krill_eaten,
+            name,
+        }
+    }
+
+    /// Calculates the krill/death ratio used to rank entries.
+    pub fn ratio(&self) -> f32 {
+        Self::ratio_of(self.krill_eaten, self.harpoon_count)
+    }
+
+    /// Calculates a krill/death ratio from raw stats, without needing an `Entry`.
+    pub fn ratio_of(krill_eaten: usize, harpoon_count: usize) -> f32 {
+        if harpoon_count == 0 {
+            f32::INFINITY
+        } else {
+            krill_eaten as f32 / harpoon_count as f32
+        }
+    }
+}
+
+/// The persisted top-ten table of rounds, sorted best-ratio-first.
+#[derive(Default, Deserialize, Serialize)]
+pub struct Leaderboard {
+    /// The recorded entries, sorted best-first.
+    entries: Vec<Entry>,
+}
+
+impl Leaderboard {
+    /// Inserts a new entry, keeping the table sorted and trimmed to `MAX_ENTRIES`.
+    pub fn insert(&mut self, entry: Entry) {
+        self.entries.push(entry);
+        self.entries.sort_by(|a, b| b.ratio().partial_cmp(&a.ratio()).unwrap_or(std::cmp::Ordering::Equal));
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    /// Loads the leaderboard from disk, returning an empty table if none exists yet.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        fs::read_to_string(path).ok().and_then(|contents| toml::from_str(&contents).ok()).unwrap_or_default()
+    }
+
+    /// Determines the path the leaderboard is persisted to.
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("whale-simulator").join("scores.toml"))
+    }
+
+    /// Checks whether a round with the given stats would earn a place on the
+    /// table. A round with no krill eaten and no harpoons survived hasn't
+    /// actually been played, so it never qualifies no matter how it ranks.
+    pub fn qualifies(&self, krill_eaten: usize, harpoon_count: usize) -> bool {
+        if krill_eaten == 0 && harpoon_count == 0 {
+            return false;
+        }
+        let ratio = Entry::ratio_of(krill_eaten, harpoon_count);
+        self.entries.len() < MAX_ENTRIES || self.entries.last().is_none_or(|worst| ratio > worst.ratio())
+    }
+
+    /// Persists the leaderboard to disk, creating the config directory if needed.
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or_else(|| "Unable to determine the config directory".to_string())?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| format!("Unable to create the config directory: {}", err))?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(|err| format!("Unable to serialize the leaderboard: {}", err))?;
+        fs::write(path, contents).map_err(|err| format!("Unable to write the leaderboard: {}", err))
+    }
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.entries.is_empty() {
+            return writeln!(f, "No scores have been recorded yet.");
+        }
+        writeln!(f, "  # Name Krill Deaths Ratio      Date")?;
+        for (position, entry) in self.entries.iter().enumerate() {
+            let ratio = if entry.ratio().is_infinite() { "\u{221E}".to_string() } else { format!("{:.3}", entry.ratio()) };
+            writeln!(f, "{: >3} {: <4} {: <5} {: <6} {: <10} {}", position + 1, entry.name, entry.krill_eaten, entry.harpoon_count, ratio, entry.date)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qualifies_rejects_a_round_with_no_engagement() {
+        let leaderboard = Leaderboard::default();
+        assert!(!leaderboard.qualifies(0, 0));
+    }
+
+    #[test]
+    fn qualifies_when_the_table_is_not_yet_full() {
+        let leaderboard = Leaderboard::default();
+        assert!(leaderboard.qualifies(1, 1));
+    }
+
+    #[test]
+    fn qualifies_beats_the_worst_entry_once_the_table_is_full() {
+        let mut leaderboard = Leaderboard::default();
+        for _ in 0..MAX_ENTRIES {
+            leaderboard.insert(Entry::new("whale".to_string(), 10, 1));
+        }
+        assert!(leaderboard.qualifies(11, 1));
+        assert!(!leaderboard.qualifies(5, 1));
+    }
+
+    #[test]
+    fn insert_keeps_entries_sorted_best_ratio_first() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(Entry::new("low".to_string(), 2, 1));
+        leaderboard.insert(Entry::new("high".to_string(), 8, 1));
+        assert_eq!(leaderboard.entries[0].name, "high");
+        assert_eq!(leaderboard.entries[1].name, "low");
+    }
+
+    #[test]
+    fn insert_truncates_to_max_entries() {
+        let mut leaderboard = Leaderboard::default();
+        for i in 0..MAX_ENTRIES + 3 {
+            leaderboard.insert(Entry::new("whale".to_string(), i, 1));
+        }
+        assert_eq!(leaderboard.entries.len(), MAX_ENTRIES);
+    }
+}
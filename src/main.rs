@@ -1,8 +1,12 @@
 // Whale Simulator (C) 2022 Sadie Powell <sadie@witchery.services>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+#[cfg(feature = "audio")]
+mod audio;
 mod entity;
 mod game;
+mod grammar;
+mod leaderboard;
 
 use std::io;
 use std::process;
@@ -16,6 +20,15 @@ use game::GameState;
 #[derive(Parser)]
 #[clap(author, version)]
 pub struct Args {
+    /// How perfectly boats lead the whale when firing harpoons, from 0.0 (dumb
+    /// straight-down shots) to 1.0 (near-perfect interception).
+    #[clap(default_value_t = 0.75, long, short, value_name = "RATIO")]
+    pub accuracy: f32,
+
+    /// Prints the high-score leaderboard and exits without playing.
+    #[clap(long)]
+    pub leaderboard: bool,
+
     /// How long a game should last for.
     #[clap(default_value_t = 600, long, short, value_name = "SECONDS")]
     pub round_length: u64,
@@ -27,9 +40,15 @@ pub struct Args {
 
 fn main() {
     let args = Args::parse();
+
+    if args.leaderboard {
+        print!("{}", leaderboard::Leaderboard::load());
+        return;
+    }
+
     let stdout = io::stdout();
 
-    let mut game = GameState::new(stdout.lock()).unwrap_or_else(|err| {
+    let mut game = GameState::new(stdout.lock(), args.accuracy).unwrap_or_else(|err| {
         eprintln!("An error occurred whilst initializing the game:");
         eprintln!("{}.", err);
         process::exit(1);